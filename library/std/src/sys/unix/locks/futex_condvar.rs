@@ -1,7 +1,7 @@
 use super::Mutex;
 use crate::sync::atomic::{AtomicU32, Ordering::Relaxed};
 use crate::sys::futex::{futex_wait, futex_wake, futex_wake_all};
-use crate::time::Duration;
+use crate::time::{Duration, Instant};
 
 pub struct Condvar {
     // The value of this atomic is simply incremented on every notification.
@@ -58,9 +58,34 @@ impl Condvar {
         // Unlock the mutex before going to sleep.
         mutex.unlock();
 
-        // Wait, but only if there hasn't been any
-        // notification since we unlocked the mutex.
-        let r = futex_wait(&self.futex, futex_value, timeout);
+        // Convert the timeout into an absolute deadline up front. `futex_wait` can
+        // return spuriously before a real notification, so we can't trust its
+        // timed-out flag; instead we loop, re-checking the notification counter and
+        // the deadline on every wakeup. The wait only counts as timed out once the
+        // deadline is actually reached with the counter unchanged. An inner `None`
+        // means the deadline overflowed `Instant`, so we treat it as infinite.
+        let deadline = timeout.map(|timeout| Instant::now().checked_add(timeout));
+        let r = loop {
+            // A notification has arrived if the counter changed since we sampled it.
+            if self.futex.load(Relaxed) != futex_value {
+                break false;
+            }
+
+            match deadline {
+                // No timeout, or a deadline that overflowed `Instant`: wait until
+                // notified.
+                None | Some(None) => {
+                    futex_wait(&self.futex, futex_value, None);
+                }
+                Some(Some(deadline)) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => {
+                        futex_wait(&self.futex, futex_value, Some(remaining));
+                    }
+                    // The deadline has passed without a notification: timed out.
+                    _ => break true,
+                },
+            }
+        };
 
         // We're no longer waiting: do this as soon as possible to avoid spurious wake calls.
         // Note that calling futex_wake unnecessarily has no effect on correctness,
@@ -0,0 +1,109 @@
+use crate::sync::atomic::{
+    AtomicU32,
+    Ordering::{Acquire, Relaxed, Release},
+};
+use crate::sys::futex::{futex_wait, futex_wake_all};
+
+pub struct RwLock {
+    // The lock state. `0` means unlocked; an even value `2 * n` means locked by
+    // `n` readers; `u32::MAX` means locked by a single writer. Because the
+    // writer sentinel is odd and every reader count is even, a reader can tell
+    // whether a writer holds the lock just by looking at the low bit.
+    state: AtomicU32,
+    // The number of threads currently blocked trying to acquire the write lock.
+    // Readers refuse to acquire while this is nonzero so that a steady stream of
+    // readers can't starve a waiting writer.
+    writers_waiting: AtomicU32,
+}
+
+const UNLOCKED: u32 = 0;
+const WRITE_LOCKED: u32 = u32::MAX;
+
+impl RwLock {
+    #[inline]
+    pub const fn new() -> Self {
+        Self { state: AtomicU32::new(UNLOCKED), writers_waiting: AtomicU32::new(0) }
+    }
+
+    #[inline]
+    pub fn try_read(&self) -> bool {
+        let state = self.state.load(Relaxed);
+        // Only even states below the writer sentinel are read-lockable, and even
+        // then we defer to any writer already waiting.
+        state != WRITE_LOCKED
+            && self.writers_waiting.load(Relaxed) == 0
+            && self.state.compare_exchange(state, state + 2, Acquire, Relaxed).is_ok()
+    }
+
+    #[inline]
+    pub fn read(&self) {
+        loop {
+            // Defer to any waiting writer so writers can't starve. The blocking
+            // condition lives in `writers_waiting`, so we must also park on that
+            // word: parking on `state` instead would miss the wakeup if a writer
+            // fully acquired and released (returning `state` to its original
+            // value) before we slept. Every decrement of `writers_waiting` is
+            // paired with a wake (see `write`), so a reader can't be stranded.
+            let writers = self.writers_waiting.load(Relaxed);
+            if writers != 0 {
+                futex_wait(&self.writers_waiting, writers, None);
+                continue;
+            }
+
+            let state = self.state.load(Relaxed);
+            if state != WRITE_LOCKED {
+                if self.state.compare_exchange_weak(state, state + 2, Acquire, Relaxed).is_ok() {
+                    return;
+                }
+            } else {
+                // A writer holds the lock; sleep until the state word changes.
+                futex_wait(&self.state, state, None);
+            }
+        }
+    }
+
+    #[inline]
+    pub unsafe fn read_unlock(&self) {
+        // If we were the last reader, wake *all* waiters. Both readers and a
+        // writer can be parked on `state`: waking only one risks waking a reader
+        // that immediately re-parks (because `writers_waiting != 0`) while the
+        // writer it's backing off for sleeps forever.
+        if self.state.fetch_sub(2, Release) == 2 {
+            futex_wake_all(&self.state);
+        }
+    }
+
+    #[inline]
+    pub fn try_write(&self) -> bool {
+        self.state.compare_exchange(UNLOCKED, WRITE_LOCKED, Acquire, Relaxed).is_ok()
+    }
+
+    #[inline]
+    pub fn write(&self) {
+        // Announce ourselves so readers back off, keeping writers from starving.
+        self.writers_waiting.fetch_add(1, Relaxed);
+        loop {
+            match self.state.compare_exchange(UNLOCKED, WRITE_LOCKED, Acquire, Relaxed) {
+                Ok(_) => {
+                    // We stopped waiting; wake any readers parked on this word so
+                    // they re-check the condition (they still can't proceed until
+                    // we release, but they must not miss the eventual transition).
+                    self.writers_waiting.fetch_sub(1, Relaxed);
+                    futex_wake_all(&self.writers_waiting);
+                    return;
+                }
+                Err(state) => {
+                    futex_wait(&self.state, state, None);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub unsafe fn write_unlock(&self) {
+        self.state.store(UNLOCKED, Release);
+        // Wake everyone: the readers parked behind us can all proceed at once,
+        // and any waiting writer will re-contend for the now-free lock.
+        futex_wake_all(&self.state);
+    }
+}
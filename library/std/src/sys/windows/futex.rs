@@ -0,0 +1,36 @@
+use super::c;
+use crate::convert::TryInto;
+use crate::sync::atomic::AtomicU32;
+use crate::time::Duration;
+
+pub fn futex_wait(futex: &AtomicU32, expected: u32, timeout: Option<Duration>) -> bool {
+    // Windows takes a relative millisecond timeout (or INFINITE), but
+    // `WaitOnAddress` can return spuriously, so the caller loops. We just
+    // translate the timeout and report whether we timed out.
+    let timeout = match timeout {
+        Some(duration) => duration.as_millis().try_into().unwrap_or(c::INFINITE - 1),
+        None => c::INFINITE,
+    };
+
+    let r = unsafe {
+        c::WaitOnAddress(
+            futex as *const AtomicU32 as *const c::c_void,
+            &expected as *const u32 as *const c::c_void,
+            crate::mem::size_of::<u32>(),
+            timeout,
+        )
+    };
+
+    // `WaitOnAddress` returns `FALSE` on timeout; anything else (a wake or a
+    // spurious return) is reported as not having timed out.
+    r != c::FALSE || unsafe { c::GetLastError() } != c::ERROR_TIMEOUT
+}
+
+pub fn futex_wake(futex: &AtomicU32) -> bool {
+    unsafe { c::WakeByAddressSingle(futex as *const AtomicU32 as *const c::c_void) };
+    false
+}
+
+pub fn futex_wake_all(futex: &AtomicU32) {
+    unsafe { c::WakeByAddressAll(futex as *const AtomicU32 as *const c::c_void) }
+}